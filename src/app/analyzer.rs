@@ -0,0 +1,226 @@
+use egui::Color32;
+use realfft::num_complex::Complex;
+
+/// Context handed to every [`Analyzer`] in [`Analyzer::finalize`], since a
+/// frequency-domain bin in isolation isn't enough to convert to physical
+/// units (Hz, dB, …) or to know how it was scaled going in.
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisContext {
+    pub sample_rate: f32,
+    pub fft_size: usize,
+    pub window_gain: f32,
+    /// Lower bound of the dB scale used to map magnitudes to intensity/color.
+    pub db_floor: f32,
+    /// Upper bound of the dB scale used to map magnitudes to intensity/color.
+    pub db_ceiling: f32,
+}
+
+impl AnalysisContext {
+    /// Converts a raw FFT magnitude into a calibrated dB value, correcting
+    /// for the window's coherent gain and normalizing by `fft_size` so the
+    /// scale stays consistent regardless of FFT size.
+    pub fn magnitude_to_db(&self, magnitude: f32) -> f32 {
+        let magnitude = magnitude / self.window_gain.max(f32::EPSILON);
+        let reference = self.fft_size as f32;
+        20.0 * (magnitude / reference).log10()
+    }
+}
+
+/// A single measurement produced by an [`Analyzer`] once a frame finishes.
+#[derive(Debug, Clone)]
+pub enum MeasurementValue {
+    /// No measurement is available yet.
+    None,
+    /// A peak tone: its frequency in Hz and its magnitude in dB.
+    PeakTone { frequency_hz: f32, magnitude_db: f32 },
+    /// A scalar measurement expressed in dB.
+    Db(f32),
+    /// A rendered row of colors, one per displayed frequency bin.
+    Row(Vec<Color32>),
+}
+
+/// Consumes the frequency-domain bins produced by the FFT each frame and
+/// turns them into a single [`MeasurementValue`].
+///
+/// The analysis thread drives every registered analyzer identically: call
+/// [`Analyzer::begin_frame`], call [`Analyzer::accum_fd_bin`] once per bin in
+/// the half-spectrum, then call [`Analyzer::finalize`]. This decouples the
+/// FFT pipeline from what's actually measured or displayed, so new
+/// measurements can be added without touching stream setup.
+pub trait Analyzer: Send {
+    /// Resets any per-frame accumulator state. The default does nothing,
+    /// for analyzers that only need the bins seen since construction.
+    fn begin_frame(&mut self) {}
+
+    /// Accumulates one frequency-domain bin. `index` is the bin's position
+    /// in the half-spectrum (`0..=fft_size/2`).
+    fn accum_fd_bin(&mut self, bin: Complex<f32>, index: usize);
+
+    /// Called once every bin for the frame has been accumulated.
+    fn finalize(&mut self, channel: &AnalysisContext);
+
+    /// The analyzer's latest measurement.
+    fn value(&self) -> MeasurementValue;
+}
+
+/// Tracks the max-magnitude bin seen in a frame and reports it as a
+/// frequency in Hz.
+#[derive(Default)]
+pub struct PeakToneAnalyzer {
+    peak_magnitude: f32,
+    peak_index: usize,
+    frequency_hz: f32,
+    magnitude_db: f32,
+}
+
+impl Analyzer for PeakToneAnalyzer {
+    fn begin_frame(&mut self) {
+        self.peak_magnitude = 0.0;
+        self.peak_index = 0;
+    }
+
+    fn accum_fd_bin(&mut self, bin: Complex<f32>, index: usize) {
+        let magnitude = bin.norm();
+        if magnitude > self.peak_magnitude {
+            self.peak_magnitude = magnitude;
+            self.peak_index = index;
+        }
+    }
+
+    fn finalize(&mut self, channel: &AnalysisContext) {
+        self.frequency_hz =
+            self.peak_index as f32 * channel.sample_rate / channel.fft_size as f32;
+        self.magnitude_db = channel
+            .magnitude_to_db(self.peak_magnitude)
+            .clamp(channel.db_floor, channel.db_ceiling);
+    }
+
+    fn value(&self) -> MeasurementValue {
+        MeasurementValue::PeakTone {
+            frequency_hz: self.frequency_hz,
+            magnitude_db: self.magnitude_db,
+        }
+    }
+}
+
+/// Estimates the noise floor as the RMS magnitude across all bins,
+/// expressed in dB.
+#[derive(Default)]
+pub struct RmsAnalyzer {
+    sum_of_squares: f32,
+    bin_count: usize,
+    db: f32,
+}
+
+impl Analyzer for RmsAnalyzer {
+    fn begin_frame(&mut self) {
+        self.sum_of_squares = 0.0;
+        self.bin_count = 0;
+    }
+
+    fn accum_fd_bin(&mut self, bin: Complex<f32>, _index: usize) {
+        self.sum_of_squares += bin.norm_sqr();
+        self.bin_count += 1;
+    }
+
+    fn finalize(&mut self, channel: &AnalysisContext) {
+        let rms = if self.bin_count > 0 {
+            (self.sum_of_squares / self.bin_count as f32).sqrt()
+        } else {
+            0.0
+        };
+        self.db = channel
+            .magnitude_to_db(rms)
+            .clamp(channel.db_floor, channel.db_ceiling);
+    }
+
+    fn value(&self) -> MeasurementValue {
+        MeasurementValue::Db(self.db)
+    }
+}
+
+/// Renders a waterfall row of colors from the frame's magnitudes, on a
+/// calibrated dB scale fixed between `channel.db_floor` and
+/// `channel.db_ceiling`. Unlike a running-max-normalized scale, this keeps
+/// the display stable as signal levels change and lets operators judge
+/// absolute signal strength across the passband.
+#[derive(Default)]
+pub struct WaterfallAnalyzer {
+    magnitudes: Vec<f32>,
+    row: Vec<Color32>,
+}
+
+impl Analyzer for WaterfallAnalyzer {
+    fn begin_frame(&mut self) {
+        self.magnitudes.clear();
+    }
+
+    fn accum_fd_bin(&mut self, bin: Complex<f32>, _index: usize) {
+        self.magnitudes.push(bin.norm());
+    }
+
+    fn finalize(&mut self, channel: &AnalysisContext) {
+        self.row = self
+            .magnitudes
+            .iter()
+            .map(|&magnitude| {
+                let db = channel
+                    .magnitude_to_db(magnitude)
+                    .clamp(channel.db_floor, channel.db_ceiling);
+                let scaled = (db - channel.db_floor) / (channel.db_ceiling - channel.db_floor);
+                let intensity = (scaled * 255.0) as u8;
+                Color32::from_rgb(intensity, intensity, intensity)
+            })
+            .collect();
+    }
+
+    fn value(&self) -> MeasurementValue {
+        MeasurementValue::Row(self.row.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(fft_size: usize, window_gain: f32) -> AnalysisContext {
+        AnalysisContext {
+            sample_rate: 48000.0,
+            fft_size,
+            window_gain,
+            db_floor: -120.0,
+            db_ceiling: 0.0,
+        }
+    }
+
+    #[test]
+    fn magnitude_equal_to_reference_is_zero_db() {
+        let context = context(1024, 1.0);
+        let db = context.magnitude_to_db(1024.0);
+        assert!(db.abs() < 1e-4, "expected ~0 dB, got {db}");
+    }
+
+    #[test]
+    fn halving_magnitude_drops_about_six_db() {
+        let context = context(1024, 1.0);
+        let full = context.magnitude_to_db(1024.0);
+        let half = context.magnitude_to_db(512.0);
+        assert!((full - half - 6.0206).abs() < 1e-3, "delta was {}", full - half);
+    }
+
+    #[test]
+    fn window_gain_corrects_the_scale() {
+        let unity = context(1024, 1.0).magnitude_to_db(512.0);
+        let attenuated = context(1024, 0.5).magnitude_to_db(512.0);
+        // Dividing by a 0.5 window gain doubles the corrected magnitude,
+        // which is a +6.0206 dB shift versus an unattenuated window.
+        assert!((attenuated - unity - 6.0206).abs() < 1e-3);
+    }
+
+    #[test]
+    fn zero_magnitude_does_not_produce_nan_or_panic() {
+        let context = context(1024, 1.0);
+        let db = context.magnitude_to_db(0.0);
+        assert!(db.is_infinite() || db < context.db_floor);
+    }
+}
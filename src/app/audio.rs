@@ -1,12 +1,27 @@
 use super::*;
 use cpal::traits::StreamTrait;
-use rustfft::num_complex::Complex;
-use rustfft::FftPlanner;
-use std::time::{Duration, Instant};
+use realfft::num_complex::Complex;
+use realfft::RealFftPlanner;
+use ringbuf::traits::{Consumer, Producer, Split};
+use ringbuf::HeapRb;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
 
 impl Js8App {
     /// Starts the audio stream for the selected input device.
+    ///
+    /// The cpal input callback only converts stereo samples to mono and
+    /// pushes them into a lock-free ring buffer — it never locks a mutex or
+    /// allocates, so it can't be blocked or starved by the UI/analysis side.
+    /// A dedicated analysis thread drains the ring buffer, runs the window
+    /// and FFT, and drives the analyzers entirely off the audio thread.
     pub fn start_audio_stream(&mut self) {
+        // Stop the previous analysis thread before starting a new one, so
+        // switching input devices doesn't leak a thread that spins forever
+        // once its ring buffer's producer has been dropped.
+        self.stop_analysis_thread();
+
         println!("Starting audio stream...");
 
         let device = self.devices[self.selected_device_index].clone();
@@ -32,38 +47,15 @@ impl Js8App {
             eprintln!("Warning: The input device does not have 2 channels");
         }
 
-        let audio_data = self.audio_data.clone();
-        let row_colors = self.row_colors.clone();
-        let max_value = self.max_value.clone();
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(self.fft_size);
-        let scratch = vec![Complex { re: 0.0, im: 0.0 }; fft.get_inplace_scratch_len()];
-
-        let mut last_update = Instant::now();
-
-        let input_callback = {
-            let audio_data = audio_data.clone();
-            let row_colors = row_colors.clone();
-            let max_value = max_value.clone();
-            let fft = fft.clone();
-            let mut scratch = scratch.clone();
-            let fft_size = self.fft_size;
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if last_update.elapsed() >= Duration::from_secs_f32(0.16) {
-                    let mut audio_data = audio_data.lock().unwrap();
-                    let mut row_colors = row_colors.lock().unwrap();
-                    let mut max_value = max_value.lock().unwrap();
-                    *max_value = Self::process_audio_data(
-                        fft_size,
-                        *max_value,
-                        data,
-                        &mut audio_data,
-                        &mut row_colors,
-                        &*fft,
-                        &mut scratch,
-                    );
-                    last_update = Instant::now();
-                }
+        // Sized generously so a slow analysis thread can't make the
+        // audio-thread push silently drop samples under normal load.
+        let ring = HeapRb::<f32>::new(self.fft_size * 4);
+        let (mut producer, mut consumer) = ring.split();
+
+        let input_callback = move |data: &[f32], _: &cpal::InputCallbackInfo| {
+            for samples in data.chunks(2) {
+                let mono_sample = (samples[0] + samples[1]) / 2.0;
+                let _ = producer.try_push(mono_sample);
             }
         };
 
@@ -86,74 +78,166 @@ impl Js8App {
         }
 
         self.stream = Some(stream);
+
+        let row_colors = self.row_colors.clone();
+        let analyzers = self.analyzers.clone();
+        let measurements = self.measurements.clone();
+        let fft_size = self.fft_size;
+        let sample_rate = self.sample_rate;
+        let window_coefficients = self.window_function.coefficients(self.fft_size);
+        let window_gain = WindowFunction::coherent_gain(&window_coefficients);
+        let db_floor = self.db_floor.clone();
+        let db_ceiling = self.db_ceiling.clone();
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_for_thread = shutdown.clone();
+
+        let handle = thread::spawn(move || {
+            let mut planner = RealFftPlanner::<f32>::new();
+            let real_fft = planner.plan_fft_forward(fft_size);
+            let mut real_fft_scratch = real_fft.make_scratch_vec();
+            let mut spectrum_buffer = real_fft.make_output_vec();
+            let mut real_input_buffer = real_fft.make_input_vec();
+            let mut frame_buffer: Vec<f32> = Vec::with_capacity(fft_size);
+
+            while !shutdown_for_thread.load(Ordering::Relaxed) {
+                match consumer.try_pop() {
+                    Some(sample) => {
+                        frame_buffer.push(sample);
+                        if frame_buffer.len() == fft_size {
+                            let mut row_colors = row_colors.lock().unwrap();
+                            let mut analyzers = analyzers.lock().unwrap();
+                            let mut measurements = measurements.lock().unwrap();
+                            // Read fresh each frame so dragging the dB
+                            // floor/ceiling sliders takes effect immediately.
+                            let db_floor = *db_floor.lock().unwrap();
+                            let db_ceiling = *db_ceiling.lock().unwrap();
+                            Self::process_audio_data(
+                                fft_size,
+                                sample_rate,
+                                &frame_buffer,
+                                &mut row_colors,
+                                &mut measurements,
+                                &*real_fft,
+                                &mut real_input_buffer,
+                                &mut spectrum_buffer,
+                                &mut real_fft_scratch,
+                                &window_coefficients,
+                                window_gain,
+                                db_floor,
+                                db_ceiling,
+                                &mut analyzers,
+                            );
+                            frame_buffer.clear();
+                        }
+                    }
+                    None => {
+                        // Nothing new yet; avoid busy-spinning the thread.
+                        thread::sleep(Duration::from_millis(5));
+                    }
+                }
+            }
+        });
+
+        self.analysis_thread_shutdown = shutdown;
+        self.analysis_thread = Some(handle);
+    }
+
+    /// Signals the running analysis thread (if any) to exit and waits for
+    /// it to finish, so restarting the stream (e.g. after switching input
+    /// devices) never leaves a thread spinning on a dead ring buffer.
+    fn stop_analysis_thread(&mut self) {
+        self.analysis_thread_shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.analysis_thread.take() {
+            let _ = handle.join();
+        }
     }
 
-    /// Processes the incoming audio data, performs FFT, and updates the visualization.
+    /// Applies the window function to a full frame of mono samples, performs
+    /// the FFT, and drives the registered analyzers with the resulting
+    /// frequency-domain bins.
     ///
     /// # Arguments
     ///
-    /// * `global_max_value` - The global maximum value for normalization.
-    /// * `data` - The incoming audio data.
-    /// * `audio_data` - The shared audio data buffer.
+    /// * `sample_rate` - The sample rate of the input device, in Hz.
+    /// * `frame` - Exactly `fft_size` mono samples.
     /// * `row_colors` - The shared color data for the rows in the visualization.
-    /// * `fft` - The FFT processor.
+    /// * `measurements` - Latest measurement from each analyzer, refreshed every frame.
+    /// * `real_fft` - The real-to-complex FFT processor. Since the input is
+    ///   purely real, this only computes the non-redundant `fft_size/2 + 1`
+    ///   bins, roughly halving the work done per frame versus a
+    ///   complex-to-complex FFT of the same size.
+    /// * `real_input_buffer` - Preallocated buffer of windowed real samples fed to `real_fft`.
+    /// * `spectrum_buffer` - Preallocated output buffer of `fft_size/2 + 1` complex bins.
     /// * `scratch` - The scratch buffer for FFT processing.
-    ///
-    /// # Returns
-    ///
-    /// The updated global maximum value.
+    /// * `window_coefficients` - Per-sample window coefficients, `fft_size` long.
+    /// * `window_gain` - The window's coherent gain, used to keep amplitude
+    ///   scaling consistent across window functions.
+    /// * `db_floor` / `db_ceiling` - The calibrated dB range analyzers map
+    ///   magnitudes into.
+    /// * `analyzers` - The analyzers to drive with this frame's bins.
+    #[allow(clippy::too_many_arguments)]
     fn process_audio_data(
         fft_size: usize,
-        global_max_value: f32,
-        data: &[f32],
-        audio_data: &mut VecDeque<f32>,
+        sample_rate: f32,
+        frame: &[f32],
         row_colors: &mut Vec<Vec<Color32>>,
-        fft: &dyn rustfft::Fft<f32>,
+        measurements: &mut Vec<MeasurementValue>,
+        real_fft: &dyn realfft::RealToComplex<f32>,
+        real_input_buffer: &mut [f32],
+        spectrum_buffer: &mut [Complex<f32>],
         scratch: &mut [Complex<f32>],
-    ) -> f32 {
-        // Convert stereo to mono and store in the audio buffer
-        for samples in data.chunks(2) {
-            if audio_data.len() == fft_size {
-                audio_data.pop_front();
-            }
-            let mono_sample = (samples[0] + samples[1]) / 2.0;
-            audio_data.push_back(mono_sample);
+        window_coefficients: &[f32],
+        window_gain: f32,
+        db_floor: f32,
+        db_ceiling: f32,
+        analyzers: &mut [Box<dyn Analyzer>],
+    ) {
+        debug_assert_eq!(frame.len(), fft_size);
+
+        // Apply the window function into the reused real input buffer
+        for ((dst, &x), &w) in real_input_buffer
+            .iter_mut()
+            .zip(frame.iter())
+            .zip(window_coefficients.iter())
+        {
+            *dst = x * w;
         }
 
-        // Perform FFT on the audio data
-        if audio_data.len() == fft_size {
-            let mut buffer: Vec<Complex<f32>> = audio_data
-                .iter()
-                .map(|&x| Complex { re: x, im: 0.0 })
-                .collect();
-            fft.process_with_scratch(&mut buffer, scratch);
-
-            // Use raw FFT values up to num_buckets
-            let raw_values: Vec<f32> = buffer.iter().map(|c| c.norm()).collect();
-
-            // Update the maximum value seen so far
-            let max_value = raw_values.iter().cloned().fold(f32::MIN, f32::max);
-
-            // Normalize the values and convert to colors
-            let normalized_values: Vec<f32> = raw_values.iter().map(|&x| x / max_value).collect();
-
-            let colors: Vec<Color32> = normalized_values
-                .iter()
-                .map(|&x| {
-                    let intensity = (x * 255.0) as u8;
-                    Color32::from_rgb(intensity, intensity, intensity)
-                })
-                .collect();
-
-            // Update the row colors
-            row_colors.push(colors);
-            if row_colors.len() > 100 {
-                row_colors.remove(0);
-            }
+        real_fft
+            .process_with_scratch(real_input_buffer, spectrum_buffer, scratch)
+            .expect("real FFT buffers are sized by make_input_vec/make_output_vec/make_scratch_vec");
+
+        let context = AnalysisContext {
+            sample_rate,
+            fft_size,
+            window_gain,
+            db_floor,
+            db_ceiling,
+        };
 
-            return max_value;
+        for analyzer in analyzers.iter_mut() {
+            analyzer.begin_frame();
+        }
+        for (index, bin) in spectrum_buffer.iter().enumerate() {
+            for analyzer in analyzers.iter_mut() {
+                analyzer.accum_fd_bin(*bin, index);
+            }
+        }
+        for analyzer in analyzers.iter_mut() {
+            analyzer.finalize(&context);
         }
 
-        global_max_value
+        measurements.clear();
+        for analyzer in analyzers.iter() {
+            let value = analyzer.value();
+            if let MeasurementValue::Row(row) = &value {
+                row_colors.push(row.clone());
+                if row_colors.len() > 100 {
+                    row_colors.remove(0);
+                }
+            }
+            measurements.push(value);
+        }
     }
 }
@@ -1,6 +1,15 @@
+pub mod analyzer;
 pub mod audio;
+pub mod transmit;
 pub mod ui;
 pub mod visualization;
+pub mod window;
+
+use analyzer::{
+    AnalysisContext, Analyzer, MeasurementValue, PeakToneAnalyzer, RmsAnalyzer, WaterfallAnalyzer,
+};
+use transmit::TxSymbol;
+use window::WindowFunction;
 
 use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::{Device, Stream};
@@ -8,20 +17,29 @@ use cpal::{Device, Stream};
 use egui::Color32;
 
 use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 
 const MAX_FREQUENCY: f32 = 3000.0;
+/// Lower bound of the displayed passband when the logarithmic frequency
+/// axis is enabled; `log10(0)` is undefined, so the axis can't start at 0 Hz.
+const MIN_DISPLAYED_FREQUENCY: f32 = 100.0;
 
 /// The main application structure for JS8App.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct Js8App {
-    /// Shared audio data buffer.
-    #[serde(skip)]
-    audio_data: Arc<Mutex<VecDeque<f32>>>,
     /// Optional audio stream.
     #[serde(skip)]
     stream: Option<Stream>,
+    /// Handle to the dedicated analysis thread draining the ring buffer, so
+    /// it can be joined instead of leaked when the stream is restarted.
+    #[serde(skip)]
+    analysis_thread: Option<JoinHandle<()>>,
+    /// Set to signal the running analysis thread to exit.
+    #[serde(skip)]
+    analysis_thread_shutdown: Arc<AtomicBool>,
     /// List of available audio input devices.
     #[serde(skip)]
     devices: Vec<Device>,
@@ -34,15 +52,48 @@ pub struct Js8App {
     /// Minimum value for normalization.
     #[serde(skip)]
     min_value: f32,
-    /// Shared maximum value for normalization.
-    #[serde(skip)]
-    max_value: Arc<Mutex<f32>>,
     /// Sample rate of the selected audio input device.
     #[serde(skip)]
     sample_rate: f32,
     /// FFT size calculated based on the sample rate.
     #[serde(skip)]
     fft_size: usize,
+    /// Windowing function applied to samples before the FFT.
+    #[serde(skip)]
+    window_function: WindowFunction,
+    /// Analyzers driven by the FFT pipeline once per frame, in registration order.
+    #[serde(skip)]
+    analyzers: Arc<Mutex<Vec<Box<dyn Analyzer>>>>,
+    /// Latest measurement from each analyzer, in the same order as `analyzers`.
+    #[serde(skip)]
+    measurements: Arc<Mutex<Vec<MeasurementValue>>>,
+    /// Lower bound of the dB scale used for colorization and readouts.
+    /// Shared with the analysis thread so dragging the slider while a
+    /// stream is running takes effect immediately, not just on restart.
+    #[serde(skip)]
+    db_floor: Arc<Mutex<f32>>,
+    /// Upper bound of the dB scale used for colorization and readouts. See
+    /// `db_floor` for why this is shared rather than a plain `f32`.
+    #[serde(skip)]
+    db_ceiling: Arc<Mutex<f32>>,
+    /// Whether the bar chart and waterfall use a logarithmic frequency axis.
+    #[serde(skip)]
+    log_frequency_axis: bool,
+    /// List of available audio output devices.
+    #[serde(skip)]
+    output_devices: Vec<Device>,
+    /// Index of the selected audio output device.
+    #[serde(skip)]
+    selected_output_device_index: usize,
+    /// Optional transmit (output) audio stream.
+    #[serde(skip)]
+    output_stream: Option<Stream>,
+    /// Queue of tone symbols waiting to be transmitted.
+    #[serde(skip)]
+    tx_queue: Arc<Mutex<VecDeque<TxSymbol>>>,
+    /// Frequency used by the test-tone button, in Hz.
+    #[serde(skip)]
+    tx_test_frequency: f32,
 }
 
 impl Default for Js8App {
@@ -60,15 +111,30 @@ impl Default for Js8App {
         let fft_size = (sample_rate / 6.25).ceil() as usize;
 
         Self {
-            audio_data: Arc::new(Mutex::new(VecDeque::with_capacity(fft_size))),
             stream: None,
+            analysis_thread: None,
+            analysis_thread_shutdown: Arc::new(AtomicBool::new(false)),
             devices,
             selected_device_index,
             row_colors: Arc::new(Mutex::new(vec![])),
             min_value: 0.0,
-            max_value: Arc::new(Mutex::new(0.0)),
             sample_rate,
             fft_size,
+            window_function: WindowFunction::default(),
+            analyzers: Arc::new(Mutex::new(vec![
+                Box::<PeakToneAnalyzer>::default() as Box<dyn Analyzer>,
+                Box::<RmsAnalyzer>::default() as Box<dyn Analyzer>,
+                Box::<WaterfallAnalyzer>::default() as Box<dyn Analyzer>,
+            ])),
+            measurements: Arc::new(Mutex::new(vec![])),
+            db_floor: Arc::new(Mutex::new(-120.0)),
+            db_ceiling: Arc::new(Mutex::new(0.0)),
+            log_frequency_axis: false,
+            output_devices: host.output_devices().unwrap().collect(),
+            selected_output_device_index: 0,
+            output_stream: None,
+            tx_queue: Arc::new(Mutex::new(VecDeque::new())),
+            tx_test_frequency: 1500.0,
         }
     }
 }
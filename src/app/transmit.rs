@@ -0,0 +1,233 @@
+use super::*;
+use cpal::traits::{DeviceTrait, StreamTrait};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// Duration of the raised-cosine ramp applied at the start and end of every
+/// symbol, to limit keying clicks.
+const RAMP_DURATION: Duration = Duration::from_millis(2);
+
+/// One scheduled tone: a frequency to emit for a duration.
+#[derive(Debug, Clone, Copy)]
+pub struct TxSymbol {
+    pub frequency_hz: f32,
+    pub duration: Duration,
+}
+
+impl Js8App {
+    /// Starts the output stream used to transmit queued tone symbols.
+    ///
+    /// The oscillator is a phase accumulator: `phase` advances by
+    /// `2*PI*freq/sample_rate` each sample and the callback emits
+    /// `sin(phase)`, wrapping `phase` at `2*PI` to avoid precision loss over
+    /// long transmissions.
+    pub fn start_transmit_stream(&mut self) {
+        println!("Starting transmit stream...");
+
+        let device = self.output_devices[self.selected_output_device_index].clone();
+        let config = match device.default_output_config() {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("Failed to get default output config: {}", err);
+                return;
+            }
+        };
+
+        let sample_rate = config.sample_rate().0 as f32;
+        let channels = config.channels() as usize;
+        let tx_queue = self.tx_queue.clone();
+
+        let mut phase = 0.0f32;
+        let mut current_symbol: Option<(TxSymbol, usize)> = None;
+
+        let output_callback = move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            for frame in data.chunks_mut(channels) {
+                let sample =
+                    Self::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, sample_rate);
+                for out in frame.iter_mut() {
+                    *out = sample;
+                }
+            }
+        };
+
+        let error_callback = move |err| {
+            eprintln!("Transmit stream error: {}", err);
+        };
+
+        let stream = match device.build_output_stream(
+            &config.into(),
+            output_callback,
+            error_callback,
+            None,
+        ) {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Failed to build output stream: {}", err);
+                return;
+            }
+        };
+
+        if let Err(err) = stream.play() {
+            eprintln!("Failed to play transmit stream: {}", err);
+            return;
+        }
+
+        self.output_stream = Some(stream);
+    }
+
+    /// Queues a single tone of the given frequency and duration for
+    /// transmission.
+    pub fn queue_tone(&mut self, frequency_hz: f32, duration: Duration) {
+        self.tx_queue
+            .lock()
+            .unwrap()
+            .push_back(TxSymbol {
+                frequency_hz,
+                duration,
+            });
+    }
+
+    /// Computes the next output sample, pulling a new symbol off the queue
+    /// when the current one finishes. Uses `try_lock` so a busy queue can
+    /// never block the realtime output callback.
+    fn next_tx_sample(
+        phase: &mut f32,
+        current_symbol: &mut Option<(TxSymbol, usize)>,
+        tx_queue: &Mutex<VecDeque<TxSymbol>>,
+        sample_rate: f32,
+    ) -> f32 {
+        if current_symbol.is_none() {
+            if let Ok(mut queue) = tx_queue.try_lock() {
+                *current_symbol = queue.pop_front().map(|symbol| (symbol, 0));
+            }
+        }
+
+        let Some((symbol, elapsed)) = current_symbol else {
+            return 0.0;
+        };
+
+        let total_samples = (symbol.duration.as_secs_f32() * sample_rate).round() as usize;
+        if total_samples == 0 || *elapsed >= total_samples {
+            *current_symbol = None;
+            return 0.0;
+        }
+
+        let ramp_samples = ((RAMP_DURATION.as_secs_f32() * sample_rate) as usize)
+            .min(total_samples / 2)
+            .max(1);
+        let envelope = if *elapsed < ramp_samples {
+            0.5 - 0.5 * (PI * *elapsed as f32 / ramp_samples as f32).cos()
+        } else if *elapsed >= total_samples - ramp_samples {
+            let remaining = total_samples - *elapsed;
+            0.5 - 0.5 * (PI * remaining as f32 / ramp_samples as f32).cos()
+        } else {
+            1.0
+        };
+
+        let sample = phase.sin() * envelope;
+
+        *phase += 2.0 * PI * symbol.frequency_hz / sample_rate;
+        if *phase >= 2.0 * PI {
+            *phase -= 2.0 * PI;
+        }
+        *elapsed += 1;
+
+        sample
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 1000.0;
+
+    #[test]
+    fn empty_queue_emits_silence() {
+        let tx_queue = Mutex::new(VecDeque::new());
+        let mut phase = 0.0;
+        let mut current_symbol = None;
+        let sample =
+            Js8App::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, SAMPLE_RATE);
+        assert_eq!(sample, 0.0);
+    }
+
+    #[test]
+    fn envelope_ramps_up_holds_and_ramps_down() {
+        // At SAMPLE_RATE = 1000 Hz, a 2ms ramp is 2 samples and a 10ms
+        // symbol is 10 samples, so the sustain region is samples 2..8.
+        let tx_queue = Mutex::new(VecDeque::from([TxSymbol {
+            frequency_hz: 0.0,
+            duration: Duration::from_millis(10),
+        }]));
+        // Start at phase = PI/2 so sin(phase) == 1.0 and, since the tone's
+        // frequency is 0 Hz, every sample directly reports the envelope.
+        let mut phase = std::f32::consts::FRAC_PI_2;
+        let mut current_symbol = None;
+
+        let envelope: Vec<f32> = (0..11)
+            .map(|_| {
+                Js8App::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, SAMPLE_RATE)
+            })
+            .collect();
+
+        assert!(envelope[0].abs() < 1e-6, "ramp-in should start at 0: {envelope:?}");
+        assert!(
+            (envelope[1] - 0.5).abs() < 1e-3,
+            "ramp-in midpoint should be ~0.5: {envelope:?}"
+        );
+        for &sample in &envelope[2..8] {
+            assert!((sample - 1.0).abs() < 1e-3, "sustain should be ~1.0: {envelope:?}");
+        }
+        assert!(
+            (envelope[9] - 0.5).abs() < 1e-3,
+            "ramp-out midpoint should be ~0.5: {envelope:?}"
+        );
+        // The symbol is exactly 10 samples (indices 0..=9); the 11th call
+        // finds the symbol exhausted and reports silence.
+        assert_eq!(envelope[10], 0.0);
+    }
+
+    #[test]
+    fn finished_symbol_is_dropped_for_the_next_queued_one() {
+        let tx_queue = Mutex::new(VecDeque::from([
+            TxSymbol {
+                frequency_hz: 100.0,
+                duration: Duration::from_millis(1),
+            },
+            TxSymbol {
+                frequency_hz: 200.0,
+                duration: Duration::from_millis(1),
+            },
+        ]));
+        let mut phase = 0.0;
+        let mut current_symbol = None;
+
+        // Exhaust the first (1ms @ 1000Hz = 1 sample) symbol.
+        Js8App::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, SAMPLE_RATE);
+        // This call finds the first symbol's single sample already elapsed
+        // and drops it, returning silence.
+        Js8App::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, SAMPLE_RATE);
+        // This call finds no symbol active and pulls the second off the queue.
+        Js8App::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, SAMPLE_RATE);
+
+        let (symbol, _) = current_symbol.expect("second symbol should now be active");
+        assert_eq!(symbol.frequency_hz, 200.0);
+    }
+
+    #[test]
+    fn phase_wraps_within_two_pi() {
+        let tx_queue = Mutex::new(VecDeque::from([TxSymbol {
+            frequency_hz: 440.0,
+            duration: Duration::from_secs(1),
+        }]));
+        let mut phase = 0.0;
+        let mut current_symbol = None;
+
+        for _ in 0..(SAMPLE_RATE as usize) {
+            Js8App::next_tx_sample(&mut phase, &mut current_symbol, &tx_queue, SAMPLE_RATE);
+            assert!((0.0..2.0 * PI).contains(&phase), "phase escaped range: {phase}");
+        }
+    }
+}
@@ -31,8 +31,91 @@ pub fn update_ui(app: &mut Js8App, ctx: &egui::Context) {
                 }
             });
 
+        egui::ComboBox::from_label("Window Function")
+            .selected_text(app.window_function.label())
+            .show_ui(ui, |ui| {
+                for window_function in WindowFunction::ALL {
+                    ui.selectable_value(
+                        &mut app.window_function,
+                        window_function,
+                        window_function.label(),
+                    );
+                }
+            });
+
+        ui.horizontal(|ui| {
+            // Edited through locals and written back so the analysis thread
+            // (which reads db_floor/db_ceiling fresh every frame) sees
+            // changes immediately rather than only on the next stream start.
+            let mut db_floor = *app.db_floor.lock().unwrap();
+            let mut db_ceiling = *app.db_ceiling.lock().unwrap();
+
+            ui.label("dB floor");
+            ui.add(egui::Slider::new(&mut db_floor, -160.0..=db_ceiling));
+            ui.label("dB ceiling");
+            ui.add(egui::Slider::new(&mut db_ceiling, db_floor..=20.0));
+
+            *app.db_floor.lock().unwrap() = db_floor;
+            *app.db_ceiling.lock().unwrap() = db_ceiling;
+        });
+
+        ui.checkbox(&mut app.log_frequency_axis, "Logarithmic frequency axis");
+
+        {
+            let measurements = app.measurements.lock().unwrap();
+            for measurement in measurements.iter() {
+                match measurement {
+                    MeasurementValue::PeakTone {
+                        frequency_hz,
+                        magnitude_db,
+                    } => {
+                        ui.label(format!(
+                            "Peak tone: {:.1} Hz ({:.1} dB)",
+                            frequency_hz, magnitude_db
+                        ));
+                    }
+                    MeasurementValue::Db(db) => {
+                        ui.label(format!("Noise floor: {:.1} dB", db));
+                    }
+                    MeasurementValue::Row(_) | MeasurementValue::None => {}
+                }
+            }
+        }
+
         // app.draw_bar_chart(ui);
         app.draw_waterfall(ui);
+
+        ui.separator();
+        ui.heading("Transmit");
+
+        egui::ComboBox::from_label("Select Output Device")
+            .selected_text(
+                app.output_devices[app.selected_output_device_index]
+                    .name()
+                    .unwrap()
+                    .to_string(),
+            )
+            .show_ui(ui, |ui| {
+                for (index, device) in app.output_devices.iter().enumerate() {
+                    ui.selectable_value(
+                        &mut app.selected_output_device_index,
+                        index,
+                        device.name().unwrap(),
+                    );
+                }
+            });
+
+        if ui.button("Start Transmit Stream").clicked() {
+            app.start_transmit_stream();
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Test tone frequency (Hz)");
+            ui.add(egui::DragValue::new(&mut app.tx_test_frequency).speed(10.0));
+            if ui.button("Send Test Tone").clicked() {
+                app.queue_tone(app.tx_test_frequency, std::time::Duration::from_secs(1));
+            }
+        });
     });
 
     ctx.request_repaint();
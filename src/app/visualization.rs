@@ -1,6 +1,41 @@
 use super::*;
 
 impl Js8App {
+    /// Maps a frequency to an x offset within `[0, width]`.
+    ///
+    /// On the linear axis this is proportional to `freq`. On the
+    /// logarithmic axis it's proportional to `log10(freq)` between
+    /// `MIN_DISPLAYED_FREQUENCY` and `MAX_FREQUENCY`, which spreads out low
+    /// frequencies at the expense of high ones.
+    fn frequency_to_x(freq: f32, width: f32, log_scale: bool) -> f32 {
+        let t = if log_scale {
+            let freq = freq.max(MIN_DISPLAYED_FREQUENCY);
+            (freq.log10() - MIN_DISPLAYED_FREQUENCY.log10())
+                / (MAX_FREQUENCY.log10() - MIN_DISPLAYED_FREQUENCY.log10())
+        } else {
+            freq / MAX_FREQUENCY
+        };
+        t.clamp(0.0, 1.0) * width
+    }
+
+    /// The x offset of the left edge of bin `index`.
+    fn bin_x(index: usize, sample_rate: f32, fft_size: usize, width: f32, log_scale: bool) -> f32 {
+        let freq = index as f32 * sample_rate / fft_size as f32;
+        Self::frequency_to_x(freq, width, log_scale)
+    }
+
+    /// Draws vertical decade gridlines (100 Hz, 1 kHz, …) for the
+    /// logarithmic frequency axis.
+    fn draw_decade_gridlines(painter: &egui::Painter, y0: f32, y1: f32, width: f32) {
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(80));
+        let mut decade_freq = 10f32.powi(MIN_DISPLAYED_FREQUENCY.log10().ceil() as i32);
+        while decade_freq <= MAX_FREQUENCY {
+            let x = Self::frequency_to_x(decade_freq, width, true);
+            painter.line_segment([egui::pos2(x, y0), egui::pos2(x, y1)], stroke);
+            decade_freq *= 10.0;
+        }
+    }
+
     /// Draws a bar chart visualization of the audio data.
     ///
     /// # Arguments
@@ -12,24 +47,38 @@ impl Js8App {
             return;
         }
 
-        // Calculate the fraction of the spectrum to display
-        let spectrum_fraction = MAX_FREQUENCY / SAMPLE_RATE;
-        let num_buckets = spectrum_fraction * FFT_SIZE as f32;
-        let bar_width = ui.available_width() / num_buckets;
+        // Calculate the fraction of the spectrum to display. `row_colors[0]`
+        // is the non-redundant half-spectrum (`fft_size / 2 + 1` bins)
+        // produced by the real-to-complex FFT, so it's clamped to the row's
+        // actual length.
+        let spectrum_fraction = MAX_FREQUENCY / self.sample_rate;
+        let num_buckets = ((spectrum_fraction * self.fft_size as f32).ceil() as usize)
+            .min(row_colors[0].len());
+        let width = ui.available_width();
         let max_height = ui.available_height();
 
         let painter = ui.painter();
 
+        if self.log_frequency_axis {
+            Self::draw_decade_gridlines(painter, 0.0, max_height, width);
+        }
+
         // Draw each bar in the bar chart
-        for (i, &color) in row_colors[0]
-            .iter()
-            .take(num_buckets.ceil() as usize)
-            .enumerate()
-        {
+        for (i, &color) in row_colors[0].iter().take(num_buckets).enumerate() {
+            let x0 = Self::bin_x(i, self.sample_rate, self.fft_size, width, self.log_frequency_axis);
+            let x1 = Self::bin_x(
+                i + 1,
+                self.sample_rate,
+                self.fft_size,
+                width,
+                self.log_frequency_axis,
+            );
+            let bar_width = (x1 - x0).max(1.0);
+
             let value = color.r() as f32 / 255.0;
             let height = max_height * value;
             let rect = egui::Rect::from_min_size(
-                egui::pos2(i as f32 * bar_width, max_height - height),
+                egui::pos2(x0, max_height - height),
                 egui::vec2(bar_width, height),
             );
             painter.rect_filled(rect, 0.0, color);
@@ -53,15 +102,22 @@ impl Js8App {
         let row_height = ui.available_height() / max_rows_to_display as f32;
         let row_width = ui.available_width();
 
-        // Calculate the fraction of the spectrum to display
-        let spectrum_fraction = MAX_FREQUENCY / SAMPLE_RATE;
-        let num_buckets = (spectrum_fraction * FFT_SIZE as f32).ceil() as usize;
+        // Calculate the fraction of the spectrum to display. Rows hold the
+        // non-redundant half-spectrum (`fft_size / 2 + 1` bins) produced by
+        // the real-to-complex FFT, so this is clamped to each row's length.
+        let spectrum_fraction = MAX_FREQUENCY / self.sample_rate;
+        let num_buckets = (spectrum_fraction * self.fft_size as f32).ceil() as usize;
 
         let painter = ui.painter();
 
+        if self.log_frequency_axis {
+            Self::draw_decade_gridlines(painter, 0.0, ui.available_height(), row_width);
+        }
+
         // Draw each row in the waterfall chart
         for (row_index, row) in row_colors.iter().rev().take(num_rows).enumerate() {
             let y_offset = row_index as f32 * row_height;
+            let num_buckets = num_buckets.min(row.len());
 
             for (col_index, &color) in row.iter().take(num_buckets).enumerate() {
                 let value = color.r() as f32 / 255.0;
@@ -71,9 +127,25 @@ impl Js8App {
                     ((1.0 - value) * 255.0) as u8,
                 );
 
+                let x0 = Self::bin_x(
+                    col_index,
+                    self.sample_rate,
+                    self.fft_size,
+                    row_width,
+                    self.log_frequency_axis,
+                );
+                let x1 = Self::bin_x(
+                    col_index + 1,
+                    self.sample_rate,
+                    self.fft_size,
+                    row_width,
+                    self.log_frequency_axis,
+                );
+                let cell_width = (x1 - x0).max(1.0);
+
                 let rect = egui::Rect::from_min_size(
-                    egui::pos2(col_index as f32 * row_width / num_buckets as f32, y_offset),
-                    egui::vec2(row_width / num_buckets as f32, row_height),
+                    egui::pos2(x0, y_offset),
+                    egui::vec2(cell_width, row_height),
                 );
                 painter.rect_filled(rect, 0.0, color);
             }
@@ -0,0 +1,100 @@
+use std::f32::consts::PI;
+
+/// Windowing function applied to the audio samples before the FFT to reduce
+/// spectral leakage.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum WindowFunction {
+    /// No windowing (equivalent to multiplying every sample by `1.0`).
+    Rectangular,
+    /// Good general-purpose compromise between resolution and leakage.
+    #[default]
+    Hann,
+    /// Slightly narrower main lobe than Hann, at the cost of higher sidelobes.
+    Hamming,
+    /// Very low sidelobes, at the cost of frequency resolution.
+    Blackman,
+}
+
+impl WindowFunction {
+    /// All variants, in the order they should appear in the UI combo box.
+    pub const ALL: [WindowFunction; 4] = [
+        WindowFunction::Rectangular,
+        WindowFunction::Hann,
+        WindowFunction::Hamming,
+        WindowFunction::Blackman,
+    ];
+
+    /// A short, human-readable label for the UI combo box.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WindowFunction::Rectangular => "Rectangular",
+            WindowFunction::Hann => "Hann",
+            WindowFunction::Hamming => "Hamming",
+            WindowFunction::Blackman => "Blackman",
+        }
+    }
+
+    /// Builds the `fft_size`-length vector of window coefficients.
+    pub fn coefficients(&self, fft_size: usize) -> Vec<f32> {
+        if fft_size <= 1 {
+            return vec![1.0; fft_size];
+        }
+
+        let n = fft_size as f32 - 1.0;
+        (0..fft_size)
+            .map(|i| {
+                let i = i as f32;
+                match self {
+                    WindowFunction::Rectangular => 1.0,
+                    WindowFunction::Hann => 0.5 - 0.5 * (2.0 * PI * i / n).cos(),
+                    WindowFunction::Hamming => 0.54 - 0.46 * (2.0 * PI * i / n).cos(),
+                    WindowFunction::Blackman => {
+                        0.42 - 0.5 * (2.0 * PI * i / n).cos() + 0.08 * (4.0 * PI * i / n).cos()
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The coherent gain of a window (the mean of its coefficients), used to
+    /// keep amplitude scaling consistent across window functions.
+    pub fn coherent_gain(coefficients: &[f32]) -> f32 {
+        if coefficients.is_empty() {
+            return 1.0;
+        }
+        coefficients.iter().sum::<f32>() / coefficients.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangular_window_is_all_ones() {
+        let coefficients = WindowFunction::Rectangular.coefficients(8);
+        assert_eq!(coefficients, vec![1.0; 8]);
+        assert_eq!(WindowFunction::coherent_gain(&coefficients), 1.0);
+    }
+
+    #[test]
+    fn hann_window_is_zero_at_the_edges_and_one_at_the_center() {
+        let coefficients = WindowFunction::Hann.coefficients(9);
+        assert!(coefficients[0].abs() < 1e-6);
+        assert!(coefficients[8].abs() < 1e-6);
+        assert!((coefficients[4] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn hann_coherent_gain_is_about_one_half() {
+        let coefficients = WindowFunction::Hann.coefficients(1024);
+        let gain = WindowFunction::coherent_gain(&coefficients);
+        assert!((gain - 0.5).abs() < 0.01, "coherent gain was {gain}");
+    }
+
+    #[test]
+    fn degenerate_fft_size_does_not_panic() {
+        assert_eq!(WindowFunction::Hann.coefficients(0), Vec::<f32>::new());
+        assert_eq!(WindowFunction::Hann.coefficients(1), vec![1.0]);
+    }
+}